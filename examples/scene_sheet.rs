@@ -0,0 +1,89 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use bevy_rapier_collider_gen::*;
+
+/// Scene sheet (or, with no path specified, assets/scene/example.ron)
+/// Illustrating how to describe a set of sprites in a RON or JSON file and load, generate
+/// colliders for, and spawn all of them with one call.
+///
+/// Controls
+/// ← ↑ ↓ → (pan camera)
+/// w (zoom in)
+/// s (zoom out)
+pub fn scene_sheet_spawn(mut commands: Commands, mut image_assets: ResMut<Assets<Image>>) {
+    let sheet_path = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "assets/scene/example.ron".into());
+    info!("Loading {}", sheet_path);
+
+    let sheet = match SceneSheet::from_file(&sheet_path) {
+        Ok(sheet) => sheet,
+        Err(e) => {
+            error!("{}", e);
+            return;
+        }
+    };
+
+    if let Err(e) = spawn_scene_sheet(&mut commands, &mut image_assets, &sheet) {
+        error!("{}", e);
+    }
+}
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            window: WindowDescriptor {
+                title: "scene sheet".to_string(),
+                ..default()
+            },
+            ..default()
+        }))
+        .insert_resource(ClearColor(Color::rgb(0.0, 0.0, 0.0)))
+        .add_plugin(RapierPhysicsPlugin::<NoUserData>::pixels_per_meter(1.0))
+        .add_plugin(RapierDebugRenderPlugin {
+            style: DebugRenderStyle {
+                collider_fixed_color: [360., 100., 100., 1.],
+                collider_dynamic_color: [360., 100., 100., 1.],
+                ..default()
+            },
+            ..default()
+        })
+        .add_startup_system(camera_spawn)
+        .add_startup_system(scene_sheet_spawn)
+        .add_system(camera_movement)
+        .run();
+}
+
+pub fn camera_spawn(mut commands: Commands) {
+    commands.spawn(Camera2dBundle::default());
+}
+
+pub fn camera_movement(
+    mut query: Query<(&Camera, &mut OrthographicProjection, &mut Transform)>,
+    keys: Res<Input<KeyCode>>,
+) {
+    for (_, mut projection, mut transform) in query.iter_mut() {
+        if keys.pressed(KeyCode::Left) {
+            transform.translation.x += 10.0;
+        }
+        if keys.pressed(KeyCode::Right) {
+            transform.translation.x -= 10.0;
+        }
+
+        if keys.pressed(KeyCode::Up) {
+            transform.translation.y -= 10.0;
+        }
+
+        if keys.pressed(KeyCode::Down) {
+            transform.translation.y += 10.0;
+        }
+
+        if keys.pressed(KeyCode::W) {
+            projection.scale -= 0.01;
+        }
+
+        if keys.pressed(KeyCode::S) {
+            projection.scale += 0.01;
+        }
+    }
+}
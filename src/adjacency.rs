@@ -0,0 +1,185 @@
+use bevy::prelude::Vec2;
+
+/// Which islands, i.e. groups of edge coordinates, touch or are close to each other. Islands are
+/// identified by their index in the groups the graph was built from
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IslandGraph {
+    contacts: Vec<IslandContact>,
+    adjacency: Vec<Vec<usize>>,
+}
+
+/// Two islands within the requested distance of each other, and the smallest distance between
+/// any of their edge coordinates
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IslandContact {
+    pub a: usize,
+    pub b: usize,
+    pub distance: f32,
+}
+
+impl IslandGraph {
+    /// Number of islands the graph was built from
+    pub fn islands(&self) -> usize {
+        self.adjacency.len()
+    }
+
+    /// Every pair of islands within the requested distance, with `a` < `b`
+    pub fn contacts(&self) -> &[IslandContact] {
+        &self.contacts
+    }
+
+    /// Indices of all the islands within the requested distance of `island`, in ascending order
+    pub fn neighbors(&self, island: usize) -> impl Iterator<Item = usize> + '_ {
+        self.adjacency.get(island).into_iter().flatten().copied()
+    }
+
+    /// Whether two islands are within the requested distance of each other
+    pub fn are_adjacent(&self, a: usize, b: usize) -> bool {
+        self.adjacency
+            .get(a)
+            .map(|n| n.binary_search(&b).is_ok())
+            .unwrap_or(false)
+    }
+
+    /// Groups islands that are connected through any chain of contacts, each group sorted by index
+    pub fn connected_groups(&self) -> Vec<Vec<usize>> {
+        let mut group_of: Vec<Option<usize>> = vec![None; self.islands()];
+        let mut groups: Vec<Vec<usize>> = vec![];
+
+        for start in 0..self.islands() {
+            if group_of[start].is_some() {
+                continue;
+            }
+
+            let mut group: Vec<usize> = vec![];
+            let mut stack: Vec<usize> = vec![start];
+            group_of[start] = Some(groups.len());
+            while let Some(island) = stack.pop() {
+                group.push(island);
+                for n in self.neighbors(island) {
+                    if group_of[n].is_none() {
+                        group_of[n] = Some(groups.len());
+                        stack.push(n);
+                    }
+                }
+            }
+
+            group.sort_unstable();
+            groups.push(group);
+        }
+
+        groups
+    }
+}
+
+/// Builds the graph of which islands are within `max_distance` pixels of each other, measured
+/// between their closest edge coordinates. Islands the edge detection split apart but that still
+/// touch diagonally are √2 (about 1.41) apart, so a `max_distance` of 1.5 finds islands that touch
+pub fn island_adjacency(groups: &[Vec<Vec2>], max_distance: f32) -> IslandGraph {
+    let bounds: Vec<Option<(Vec2, Vec2)>> = groups.iter().map(|g| bounding_box(g)).collect();
+    let mut contacts: Vec<IslandContact> = vec![];
+    let mut adjacency: Vec<Vec<usize>> = vec![vec![]; groups.len()];
+
+    for a in 0..groups.len() {
+        for b in (a + 1)..groups.len() {
+            let (bounds_a, bounds_b) = match (bounds[a], bounds[b]) {
+                (Some(bounds_a), Some(bounds_b)) => (bounds_a, bounds_b),
+                _ => continue,
+            };
+            if box_gap(bounds_a, bounds_b) > max_distance {
+                continue;
+            }
+
+            let distance = closest_distance(&groups[a], &groups[b]);
+            if distance <= max_distance {
+                contacts.push(IslandContact { a, b, distance });
+                adjacency[a].push(b);
+                adjacency[b].push(a);
+            }
+        }
+    }
+
+    for neighbors in &mut adjacency {
+        neighbors.sort_unstable();
+    }
+
+    IslandGraph {
+        contacts,
+        adjacency,
+    }
+}
+
+/// smallest and largest x,y of a group of points, None if there aren't any
+fn bounding_box(points: &[Vec2]) -> Option<(Vec2, Vec2)> {
+    let first = *points.first()?;
+    Some(
+        points
+            .iter()
+            .fold((first, first), |(min, max), p| (min.min(*p), max.max(*p))),
+    )
+}
+
+/// distance between two bounding boxes, zero when they overlap
+fn box_gap(a: (Vec2, Vec2), b: (Vec2, Vec2)) -> f32 {
+    let gap = (a.0 - b.1).max(b.0 - a.1).max(Vec2::ZERO);
+    gap.length()
+}
+
+/// smallest distance between any point in `a` and any point in `b`
+fn closest_distance(a: &[Vec2], b: &[Vec2]) -> f32 {
+    a.iter()
+        .flat_map(|p| b.iter().map(move |q| p.distance_squared(*q)))
+        .fold(f32::INFINITY, f32::min)
+        .sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square(x: f32, y: f32) -> Vec<Vec2> {
+        vec![
+            Vec2::new(x, y),
+            Vec2::new(x + 1.0, y),
+            Vec2::new(x + 1.0, y + 1.0),
+            Vec2::new(x, y + 1.0),
+        ]
+    }
+
+    #[test]
+    fn diagonal_neighbors_touch_at_one_and_a_half() {
+        let groups = vec![square(0.0, 0.0), square(2.0, 2.0)];
+
+        let graph = island_adjacency(&groups, 1.5);
+        assert!(graph.are_adjacent(0, 1));
+        assert!(graph.are_adjacent(1, 0));
+        assert_eq!(graph.contacts().len(), 1);
+        assert!((graph.contacts()[0].distance - 2f32.sqrt()).abs() < 1e-6);
+
+        let graph = island_adjacency(&groups, 1.0);
+        assert!(!graph.are_adjacent(0, 1));
+        assert!(graph.contacts().is_empty());
+    }
+
+    #[test]
+    fn empty_group_is_skipped() {
+        let groups = vec![square(0.0, 0.0), vec![], square(1.0, 0.0)];
+        let graph = island_adjacency(&groups, 1.5);
+        assert_eq!(graph.islands(), 3);
+        assert_eq!(graph.neighbors(1).count(), 0);
+        assert!(graph.are_adjacent(0, 2));
+    }
+
+    #[test]
+    fn connected_groups_follow_chains() {
+        let groups = vec![
+            square(0.0, 0.0),
+            square(20.0, 0.0),
+            square(2.0, 0.0),
+            square(4.0, 0.0),
+        ];
+        let graph = island_adjacency(&groups, 1.5);
+        assert!(!graph.are_adjacent(0, 3));
+        assert_eq!(graph.connected_groups(), vec![vec![0, 2, 3], vec![1]]);
+    }
+}
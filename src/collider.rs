@@ -5,6 +5,37 @@ use crate::{
 use bevy::prelude::{Image, Vec2};
 use bevy_rapier2d::prelude::{Collider, Real};
 
+/// The kinds of bevy_rapier2d collider this crate knows how to generate from edge coordinates
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "scene", derive(serde::Serialize, serde::Deserialize))]
+pub enum ColliderShape {
+    Polyline,
+    ConvexPolyline,
+    ConvexHull,
+    Heightfield,
+}
+
+/// Generate a bevy_rapier2d collider of the given shape from a group of edge coordinates,
+/// convex shapes give back None when rapier can't build them from the coordinates, including
+/// when there are fewer than 2 coordinates or they're all the same point
+pub fn collider_from_edge(shape: ColliderShape, points: Vec<Vec2>) -> Option<Collider> {
+    let convex = matches!(
+        shape,
+        ColliderShape::ConvexPolyline | ColliderShape::ConvexHull
+    );
+    // rapier's convex hull panics rather than giving back None for these
+    if convex && (points.len() < 2 || points.iter().all(|p| *p == points[0])) {
+        return None;
+    }
+
+    match shape {
+        ColliderShape::Polyline => Some(Collider::polyline(points, None)),
+        ColliderShape::ConvexPolyline => Collider::convex_polyline(points),
+        ColliderShape::ConvexHull => Collider::convex_hull(&points),
+        ColliderShape::Heightfield => Some(heightfield_collider_from_points(&points)),
+    }
+}
+
 /// Generate a single bevy_rapier2d polyline collider from the image,
 /// coordinates translated to either side of (0, 0)
 pub fn single_polyline_collider_translated(image: &Image) -> Collider {
@@ -1,3 +1,4 @@
+use bevy::math::DVec2;
 use bevy::prelude::{Image, Vec2};
 
 /// If there's only one sprite / object in the image, this returns just one, with
@@ -24,9 +25,37 @@ pub fn multi_image_edges_raw(image: &Image) -> Vec<Vec<Vec2>> {
     image_to_edges(image, false)
 }
 
+/// Resamples a group of edge coordinates so consecutive points are roughly `segment_length` apart,
+/// measured along the path. The first and last points stay where they are, the rest are evenly
+/// spaced between them. A path is never split into more than about a million segments, however
+/// small `segment_length` is
+pub fn resample_edge(points: &[Vec2], segment_length: f32) -> Vec<Vec2> {
+    let points: Vec<DVec2> = points.iter().map(|p| p.as_dvec2()).collect();
+    resample_points(&points, segment_length as f64)
+        .into_iter()
+        .map(|p| p.as_vec2())
+        .collect()
+}
+
+/// Resamples every group of edge coordinates so consecutive points are roughly `segment_length`
+/// apart, see `resample_edge`
+pub fn resample_edges(groups: Vec<Vec<Vec2>>, segment_length: f32) -> Vec<Vec<Vec2>> {
+    groups
+        .into_iter()
+        .map(|g| resample_edge(&g, segment_length))
+        .collect()
+}
+
 /// Takes a Bevy Image type and an boolean to indicate whether to translate
 /// the points you get back to either side of (0, 0) instead of everything in positive x and y
 pub fn image_to_edges(image: &Image, translate: bool) -> Vec<Vec<Vec2>> {
+    let (processed, rows, cols) = image_to_pixels(image);
+    march_edges(&processed, rows, cols, translate)
+}
+
+/// Combines the bytes of each pixel in the image into one value that's zero when the
+/// pixel is empty, returned with the image's rows and cols
+pub(crate) fn image_to_pixels(image: &Image) -> (Vec<usize>, usize, usize) {
     let rows = (image.size().y) as usize;
     let cols = (image.size().x) as usize;
     let data: Vec<u8> = image.data.clone();
@@ -44,7 +73,7 @@ pub fn image_to_edges(image: &Image, translate: bool) -> Vec<Vec<Vec2>> {
         processed.push(b);
     }
 
-    march_edges(&processed, rows, cols, translate)
+    (processed, rows, cols)
 }
 
 /// Marching squares adjacent, walks all the pixels in the provided data and keeps track of
@@ -54,6 +83,13 @@ pub fn image_to_edges(image: &Image, translate: bool) -> Vec<Vec<Vec2>> {
 /// Accepts a flag indicating whether or not to translate coordinates to either side of (0,0)
 /// or leave it all in positive x,y
 pub fn march_edges(data: &[usize], rows: usize, cols: usize, translate: bool) -> Vec<Vec<Vec2>> {
+    let edge_points = find_edge_points(data, rows, cols);
+    points_to_drawing_order(&edge_points, translate, rows, cols)
+}
+
+/// The marching half of `march_edges`, every pixel with at least one transparent / zero value
+/// neighbor, in no particular order
+pub(crate) fn find_edge_points(data: &[usize], rows: usize, cols: usize) -> Vec<Vec2> {
     let mut edge_points: Vec<Vec2> = vec![];
 
     for d in 0..data.len() {
@@ -82,14 +118,14 @@ pub fn march_edges(data: &[usize], rows: usize, cols: usize, translate: bool) ->
         }
     }
 
-    points_to_drawing_order(&edge_points, translate, rows, cols)
+    edge_points
 }
 
 /// Takes a collection of coordinates and attempts to sort them according to drawing order
 ///
 /// Pixel sorted so that the distance to previous and next is 1. When there is no pixel left
 /// with distance 1, another group is created and sorted the same way.
-fn points_to_drawing_order(
+pub(crate) fn points_to_drawing_order(
     points: &[Vec2],
     translate: bool,
     rows: usize,
@@ -136,6 +172,45 @@ fn points_to_drawing_order(
     groups
 }
 
+/// Most segments a single resample will split a path into, so a tiny `segment_length` can't ask
+/// for more points than fit in memory. Paths that would need more are spaced a bit wider instead
+const MAX_RESAMPLED_SEGMENTS: usize = 1 << 20;
+
+/// walks along the points, placing a new one every `segment_length`, see `resample_edge`.
+/// Works in f64 so long paths don't pile up error summing their length
+pub(crate) fn resample_points(points: &[DVec2], segment_length: f64) -> Vec<DVec2> {
+    if points.len() < 2 || segment_length.is_nan() || segment_length <= 0.0 {
+        return points.to_vec();
+    }
+
+    let total: f64 = points.windows(2).map(|w| w[0].distance(w[1])).sum();
+    if total == 0.0 {
+        return points.to_vec();
+    }
+
+    let segments = ((total / segment_length).round().max(1.0) as usize).min(MAX_RESAMPLED_SEGMENTS);
+    let step = total / segments as f64;
+
+    let mut resampled: Vec<DVec2> = vec![points[0]];
+    let mut travelled: f64 = 0.0;
+    for w in points.windows(2) {
+        let len = w[0].distance(w[1]);
+        if len == 0.0 {
+            continue;
+        }
+
+        let mut next = step * resampled.len() as f64;
+        while resampled.len() < segments && next <= travelled + len {
+            resampled.push(w[0].lerp(w[1], (next - travelled) / len));
+            next = step * resampled.len() as f64;
+        }
+        travelled += len;
+    }
+    resampled.push(*points.last().unwrap());
+
+    resampled
+}
+
 /// conceptual helper, access a 1D vector like it's a 2D vector
 fn get_xy(idx: usize, offset: usize) -> (f32, f32) {
     let quot = idx / offset;
@@ -173,3 +248,63 @@ fn xy_translate(p: Vec2, rows: usize, cols: usize) -> Vec2 {
 pub fn translate_vec(v: Vec<Vec2>, rows: usize, cols: usize) -> Vec<Vec2> {
     v.into_iter().map(|p| xy_translate(p, rows, cols)).collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(len: usize) -> Vec<DVec2> {
+        (0..=len).map(|x| DVec2::new(x as f64, 0.0)).collect()
+    }
+
+    #[test]
+    fn resample_straight_line_evenly() {
+        let resampled = resample_points(&line(10), 2.0);
+        assert_eq!(resampled.len(), 6);
+        for (i, p) in resampled.iter().enumerate() {
+            assert!((p.x - 2.0 * i as f64).abs() < 1e-9);
+            assert_eq!(p.y, 0.0);
+        }
+    }
+
+    #[test]
+    fn resample_keeps_endpoints() {
+        let points = vec![
+            DVec2::new(0.0, 0.0),
+            DVec2::new(3.0, 0.0),
+            DVec2::new(3.0, 7.0),
+        ];
+        let resampled = resample_points(&points, 3.0);
+        assert_eq!(resampled.first(), points.first());
+        assert_eq!(resampled.last(), points.last());
+    }
+
+    #[test]
+    fn resample_bad_segment_length_is_unchanged() {
+        let points = line(10);
+        assert_eq!(resample_points(&points, 0.0), points);
+        assert_eq!(resample_points(&points, -1.0), points);
+        assert_eq!(resample_points(&points, f64::NAN), points);
+    }
+
+    #[test]
+    fn resample_too_few_points_is_unchanged() {
+        assert!(resample_points(&[], 2.0).is_empty());
+        let single = vec![DVec2::new(4.0, 2.0)];
+        assert_eq!(resample_points(&single, 2.0), single);
+    }
+
+    #[test]
+    fn resample_longer_than_path_gives_endpoints() {
+        let resampled = resample_points(&line(10), 100.0);
+        assert_eq!(resampled, vec![DVec2::ZERO, DVec2::new(10.0, 0.0)]);
+    }
+
+    #[test]
+    fn resample_tiny_segment_length_is_capped() {
+        let resampled = resample_points(&line(10), f32::MIN_POSITIVE as f64);
+        assert_eq!(resampled.len(), MAX_RESAMPLED_SEGMENTS + 1);
+        assert_eq!(resampled.first(), Some(&DVec2::ZERO));
+        assert_eq!(resampled.last(), Some(&DVec2::new(10.0, 0.0)));
+    }
+}
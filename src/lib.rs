@@ -1,8 +1,18 @@
 #![doc = include_str!("../README.md")]
 
+mod adjacency;
 mod collider;
 mod edge;
+#[cfg(feature = "f64")]
+mod precision;
+mod report;
+#[cfg(feature = "scene")]
+mod scene;
 
+pub use crate::adjacency::island_adjacency;
+pub use crate::adjacency::IslandContact;
+pub use crate::adjacency::IslandGraph;
+pub use crate::collider::collider_from_edge;
 pub use crate::collider::multi_convex_hull_collider_raw;
 pub use crate::collider::multi_convex_hull_collider_translated;
 pub use crate::collider::multi_convex_polyline_collider_raw;
@@ -19,9 +29,38 @@ pub use crate::collider::single_heightfield_collider_raw;
 pub use crate::collider::single_heightfield_collider_translated;
 pub use crate::collider::single_polyline_collider_raw;
 pub use crate::collider::single_polyline_collider_translated;
+pub use crate::collider::ColliderShape;
 pub use crate::edge::image_to_edges;
 pub use crate::edge::multi_image_edge_translated;
 pub use crate::edge::multi_image_edges_raw;
+pub use crate::edge::resample_edge;
+pub use crate::edge::resample_edges;
 pub use crate::edge::single_image_edge_raw;
 pub use crate::edge::single_image_edge_translated;
 pub use crate::edge::translate_vec;
+#[cfg(feature = "f64")]
+pub use crate::precision::collider_from_edge_f64;
+#[cfg(feature = "f64")]
+pub use crate::precision::collider_origin_f64;
+#[cfg(feature = "f64")]
+pub use crate::precision::multi_image_edges_f64;
+#[cfg(feature = "f64")]
+pub use crate::precision::resample_edge_f64;
+#[cfg(feature = "f64")]
+pub use crate::precision::resample_edges_f64;
+#[cfg(feature = "f64")]
+pub use crate::precision::single_image_edge_f64;
+pub use crate::report::multi_collider_with_report;
+pub use crate::report::GenerationReport;
+pub use crate::report::IslandOutcome;
+pub use crate::report::IslandReport;
+#[cfg(feature = "scene")]
+pub use crate::scene::spawn_scene_sheet;
+#[cfg(feature = "scene")]
+pub use crate::scene::BodyType;
+#[cfg(feature = "scene")]
+pub use crate::scene::SceneSheet;
+#[cfg(feature = "scene")]
+pub use crate::scene::SceneSheetError;
+#[cfg(feature = "scene")]
+pub use crate::scene::SceneSprite;
@@ -0,0 +1,156 @@
+use crate::edge::resample_points;
+use crate::{collider_from_edge, image_to_edges, ColliderShape};
+use bevy::math::DVec2;
+use bevy::prelude::{Image, Vec2};
+use bevy_rapier2d::prelude::Collider;
+
+/// If there's only one sprite / object in the image, this returns just one, in f64 world space,
+/// i.e. with `offset` (where the image sits in the world) added to every coordinate
+pub fn single_image_edge_f64(image: &Image, translate: bool, offset: DVec2) -> Vec<DVec2> {
+    multi_image_edges_f64(image, translate, offset)
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+/// If there's more than one sprite / object in the image, this returns all it finds, in f64
+/// world space, i.e. with `offset` (where the image sits in the world) added to every coordinate
+///
+/// Pixel coordinates are whole or half numbers, so they're exact in f32, widening them and adding
+/// the offset in f64 keeps them exact however far from the origin the image is
+pub fn multi_image_edges_f64(image: &Image, translate: bool, offset: DVec2) -> Vec<Vec<DVec2>> {
+    image_to_edges(image, translate)
+        .into_iter()
+        .map(|g| g.into_iter().map(|p| p.as_dvec2() + offset).collect())
+        .collect()
+}
+
+/// Resamples a group of f64 edge coordinates so consecutive points are roughly `segment_length`
+/// apart, see `resample_edge`
+pub fn resample_edge_f64(points: &[DVec2], segment_length: f64) -> Vec<DVec2> {
+    resample_points(points, segment_length)
+}
+
+/// Resamples every group of f64 edge coordinates so consecutive points are roughly
+/// `segment_length` apart, see `resample_edge`
+pub fn resample_edges_f64(groups: Vec<Vec<DVec2>>, segment_length: f64) -> Vec<Vec<DVec2>> {
+    groups
+        .into_iter()
+        .map(|g| resample_points(&g, segment_length))
+        .collect()
+}
+
+/// Where to put the body for a collider built with `collider_from_edge_f64` from these f64 world
+/// space edge coordinates. Heightfields are centred horizontally on their body by rapier, so for
+/// those it's the middle of the island's x range, for everything else it's the first coordinate
+pub fn collider_origin_f64(shape: ColliderShape, points: &[DVec2]) -> DVec2 {
+    let first = match points.first() {
+        Some(first) => *first,
+        None => return DVec2::ZERO,
+    };
+
+    match shape {
+        ColliderShape::Heightfield => {
+            let (min_x, max_x) = points.iter().fold((first.x, first.x), |(min, max), p| {
+                (min.min(p.x), max.max(p.x))
+            });
+            DVec2::new((min_x + max_x) / 2.0, first.y)
+        }
+        _ => first,
+    }
+}
+
+/// Generate a bevy_rapier2d collider of the given shape from a group of f64 world space edge
+/// coordinates. The coordinates are made relative to `origin` while still in f64 and only then
+/// narrowed to rapier's Real, so spawn the collider's body at `origin`. Use
+/// `collider_origin_f64` to pick it, an origin close to the coordinates keeps the collider's own
+/// vertices small and precise, and heightfields need the middle of the island's x range
+pub fn collider_from_edge_f64(
+    shape: ColliderShape,
+    points: &[DVec2],
+    origin: DVec2,
+) -> Option<Collider> {
+    let local: Vec<Vec2> = points.iter().map(|p| (*p - origin).as_vec2()).collect();
+    collider_from_edge(shape, local)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::multi_image_edge_translated;
+    use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+
+    /// 8x8 image with a 4x3 opaque block
+    fn block() -> Image {
+        let mut data = vec![0u8; 8 * 8 * 4];
+        for y in 2..5 {
+            for x in 2..6 {
+                let i = (y * 8 + x) * 4;
+                data[i..i + 4].copy_from_slice(&[255, 255, 255, 255]);
+            }
+        }
+        Image::new(
+            Extent3d {
+                width: 8,
+                height: 8,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data,
+            TextureFormat::Rgba8UnormSrgb,
+        )
+    }
+
+    #[test]
+    fn far_from_origin_is_exact() {
+        let image = block();
+        let offset = DVec2::new(1e7, 1e7 + 0.5);
+        let local = multi_image_edge_translated(&image);
+        let world = multi_image_edges_f64(&image, true, offset);
+        assert_eq!(local.len(), world.len());
+
+        let mut f32_is_off = false;
+        for (local, world) in local.iter().flatten().zip(world.iter().flatten()) {
+            assert_eq!((*world - offset).as_vec2(), *local);
+            // the same placement done in f32 loses the half pixel
+            let f32_world = (*local + offset.as_vec2()).as_dvec2();
+            f32_is_off |= f32_world - offset != local.as_dvec2();
+        }
+        assert!(f32_is_off);
+    }
+
+    #[test]
+    fn heightfield_origin_is_horizontal_centre() {
+        let points = vec![
+            DVec2::new(10.0, 5.0),
+            DVec2::new(14.0, 3.0),
+            DVec2::new(12.0, 4.0),
+        ];
+        assert_eq!(
+            collider_origin_f64(ColliderShape::Heightfield, &points),
+            DVec2::new(12.0, 5.0)
+        );
+        assert_eq!(
+            collider_origin_f64(ColliderShape::Polyline, &points),
+            DVec2::new(10.0, 5.0)
+        );
+        assert_eq!(
+            collider_origin_f64(ColliderShape::Heightfield, &[]),
+            DVec2::ZERO
+        );
+    }
+
+    #[test]
+    fn heightfield_lines_up_with_outline() {
+        let offset = DVec2::new(1e7, 1e7 + 0.5);
+        let world = single_image_edge_f64(&block(), true, offset);
+        let origin = collider_origin_f64(ColliderShape::Heightfield, &world);
+        let collider = collider_from_edge_f64(ColliderShape::Heightfield, &world, origin).unwrap();
+
+        let aabb = collider.raw.compute_local_aabb();
+        let min_x = world.iter().map(|p| p.x).fold(f64::INFINITY, f64::min);
+        let max_x = world.iter().map(|p| p.x).fold(f64::NEG_INFINITY, f64::max);
+        assert_eq!(aabb.mins.x as f64, min_x - origin.x);
+        assert_eq!(aabb.maxs.x as f64, max_x - origin.x);
+    }
+}
@@ -0,0 +1,303 @@
+use crate::edge::{find_edge_points, image_to_pixels, points_to_drawing_order};
+use crate::{collider_from_edge, resample_edge, ColliderShape};
+use bevy::prelude::Image;
+use bevy::utils::{Duration, Instant};
+use bevy_rapier2d::prelude::Collider;
+use std::fmt;
+
+/// What happened while generating colliders for an image: how long each stage took, how many
+/// vertices there were before and after resampling, and what came out for every island
+#[derive(Debug, Clone, PartialEq)]
+pub struct GenerationReport {
+    /// reading the image and finding every edge pixel
+    pub edge_detection: Duration,
+    /// sorting the edge pixels into drawing order and grouping them into islands
+    pub drawing_order: Duration,
+    /// resampling the edge coordinates, zero when not resampling or building heightfields
+    pub resampling: Duration,
+    /// building the bevy_rapier2d colliders
+    pub collider_building: Duration,
+    /// edge coordinates found in the image, across all islands
+    pub edge_vertices: usize,
+    /// edge coordinates handed to rapier, across all islands. The same as `edge_vertices` when
+    /// not resampling or building heightfields
+    pub resampled_vertices: usize,
+    /// one entry per island, in the same order as the colliders
+    pub islands: Vec<IslandReport>,
+}
+
+/// What happened to one island, i.e. one group of edge coordinates
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IslandReport {
+    /// edge coordinates found for the island
+    pub edge_vertices: usize,
+    /// edge coordinates handed to rapier, the same as `edge_vertices` when not resampling or
+    /// building a heightfield
+    pub resampled_vertices: usize,
+    /// the kind of collider asked for
+    pub shape: ColliderShape,
+    /// whether a collider came out, and if not why
+    pub outcome: IslandOutcome,
+}
+
+/// Whether a collider came out for an island, and if not why
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IslandOutcome {
+    /// the collider was built
+    Generated,
+    /// a convex shape was asked for but the island has fewer than 2 vertices
+    TooFewVertices,
+    /// rapier couldn't build the convex shape from 2 or more vertices, e.g. because they're
+    /// all the same point
+    Degenerate,
+}
+
+impl IslandOutcome {
+    /// classify a collider built from `vertices` edge coordinates
+    fn classify(collider: Option<&Collider>, vertices: usize) -> Self {
+        if collider.is_some() {
+            IslandOutcome::Generated
+        } else if vertices < 2 {
+            IslandOutcome::TooFewVertices
+        } else {
+            IslandOutcome::Degenerate
+        }
+    }
+
+    fn describe(self) -> &'static str {
+        match self {
+            IslandOutcome::Generated => "generated",
+            IslandOutcome::TooFewVertices => "failed, too few vertices",
+            IslandOutcome::Degenerate => "failed, degenerate",
+        }
+    }
+}
+
+impl GenerationReport {
+    /// Time spent across all stages
+    pub fn total(&self) -> Duration {
+        self.edge_detection + self.drawing_order + self.resampling + self.collider_building
+    }
+
+    /// Number of islands rapier couldn't build a collider for
+    pub fn failed_islands(&self) -> usize {
+        self.islands
+            .iter()
+            .filter(|i| i.outcome != IslandOutcome::Generated)
+            .count()
+    }
+}
+
+impl fmt::Display for GenerationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} islands ({} failed), {} -> {} vertices in {:?}",
+            self.islands.len(),
+            self.failed_islands(),
+            self.edge_vertices,
+            self.resampled_vertices,
+            self.total()
+        )?;
+        write!(
+            f,
+            "  edge detection {:?}, drawing order {:?}, resampling {:?}, collider building {:?}",
+            self.edge_detection, self.drawing_order, self.resampling, self.collider_building
+        )?;
+        for (i, island) in self.islands.iter().enumerate() {
+            write!(
+                f,
+                "\n  island {}: {:?}, {} -> {} vertices, {}",
+                i,
+                island.shape,
+                island.edge_vertices,
+                island.resampled_vertices,
+                island.outcome.describe()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Generate as many bevy_rapier2d colliders of the given shape as it can find in the image, like
+/// the multi_* functions, along with a report on how it went. Optionally resamples every island to
+/// roughly `resample` segment length first, see `resample_edge`. Heightfields need one height per
+/// pixel column so they're never resampled
+pub fn multi_collider_with_report(
+    image: &Image,
+    shape: ColliderShape,
+    translate: bool,
+    resample: Option<f32>,
+) -> (Vec<Option<Collider>>, GenerationReport) {
+    let start = Instant::now();
+    let (data, rows, cols) = image_to_pixels(image);
+    let edge_points = find_edge_points(&data, rows, cols);
+    let edge_detection = start.elapsed();
+
+    let start = Instant::now();
+    let groups = points_to_drawing_order(&edge_points, translate, rows, cols);
+    let drawing_order = start.elapsed();
+
+    let vertices: Vec<usize> = groups.iter().map(|g| g.len()).collect();
+
+    let (groups, resampling) = match resample.filter(|_| shape != ColliderShape::Heightfield) {
+        Some(segment_length) => {
+            let start = Instant::now();
+            let groups: Vec<_> = groups
+                .iter()
+                .map(|g| resample_edge(g, segment_length))
+                .collect();
+            (groups, start.elapsed())
+        }
+        None => (groups, Duration::ZERO),
+    };
+
+    let resampled_vertices: Vec<usize> = groups.iter().map(|g| g.len()).collect();
+
+    let start = Instant::now();
+    let colliders: Vec<Option<Collider>> = groups
+        .into_iter()
+        .map(|g| collider_from_edge(shape, g))
+        .collect();
+    let collider_building = start.elapsed();
+
+    let islands: Vec<IslandReport> = colliders
+        .iter()
+        .zip(vertices.iter().zip(resampled_vertices.iter()))
+        .map(|(collider, (before, after))| IslandReport {
+            edge_vertices: *before,
+            resampled_vertices: *after,
+            shape,
+            outcome: IslandOutcome::classify(collider.as_ref(), *after),
+        })
+        .collect();
+
+    let report = GenerationReport {
+        edge_detection,
+        drawing_order,
+        resampling,
+        collider_building,
+        edge_vertices: vertices.iter().sum(),
+        resampled_vertices: resampled_vertices.iter().sum(),
+        islands,
+    };
+
+    (colliders, report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::prelude::Vec2;
+    use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+
+    /// 8x8 image with two separate 2x2 opaque squares
+    fn two_squares() -> Image {
+        let mut data = vec![0u8; 8 * 8 * 4];
+        for (x, y) in [
+            (1, 1),
+            (2, 1),
+            (1, 2),
+            (2, 2),
+            (5, 5),
+            (6, 5),
+            (5, 6),
+            (6, 6),
+        ] {
+            let i = (y * 8 + x) * 4;
+            data[i..i + 4].copy_from_slice(&[255, 255, 255, 255]);
+        }
+        Image::new(
+            Extent3d {
+                width: 8,
+                height: 8,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data,
+            TextureFormat::Rgba8UnormSrgb,
+        )
+    }
+
+    #[test]
+    fn too_few_vertices_vs_degenerate() {
+        for shape in [ColliderShape::ConvexPolyline, ColliderShape::ConvexHull] {
+            let one = vec![Vec2::new(1.0, 1.0)];
+            let collider = collider_from_edge(shape, one);
+            assert_eq!(
+                IslandOutcome::classify(collider.as_ref(), 1),
+                IslandOutcome::TooFewVertices
+            );
+
+            let same = vec![Vec2::new(1.0, 1.0); 3];
+            let collider = collider_from_edge(shape, same);
+            assert_eq!(
+                IslandOutcome::classify(collider.as_ref(), 3),
+                IslandOutcome::Degenerate
+            );
+        }
+    }
+
+    #[test]
+    fn rapier_builds_convex_shapes_from_segments() {
+        // 2 points and 3 collinear points come out of rapier as flat shapes, not failures
+        let two = vec![Vec2::new(0.0, 0.0), Vec2::new(1.0, 0.0)];
+        let collinear = vec![
+            Vec2::new(0.0, 0.0),
+            Vec2::new(1.0, 0.0),
+            Vec2::new(2.0, 0.0),
+        ];
+        for shape in [ColliderShape::ConvexPolyline, ColliderShape::ConvexHull] {
+            for points in [two.clone(), collinear.clone()] {
+                let vertices = points.len();
+                let collider = collider_from_edge(shape, points);
+                assert_eq!(
+                    IslandOutcome::classify(collider.as_ref(), vertices),
+                    IslandOutcome::Generated
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn sums_match_islands() {
+        let (colliders, report) =
+            multi_collider_with_report(&two_squares(), ColliderShape::Polyline, true, Some(0.5));
+        assert_eq!(colliders.len(), 2);
+        assert_eq!(report.islands.len(), 2);
+        assert_eq!(
+            report.edge_vertices,
+            report
+                .islands
+                .iter()
+                .map(|i| i.edge_vertices)
+                .sum::<usize>()
+        );
+        assert_eq!(
+            report.resampled_vertices,
+            report
+                .islands
+                .iter()
+                .map(|i| i.resampled_vertices)
+                .sum::<usize>()
+        );
+        assert!(report.resampled_vertices > report.edge_vertices);
+    }
+
+    #[test]
+    fn heightfields_are_not_resampled() {
+        let (_, report) =
+            multi_collider_with_report(&two_squares(), ColliderShape::Heightfield, true, Some(0.5));
+        assert_eq!(report.edge_vertices, report.resampled_vertices);
+        assert_eq!(report.resampling, Duration::ZERO);
+    }
+
+    #[test]
+    fn display_has_no_trailing_newline() {
+        let (_, report) =
+            multi_collider_with_report(&two_squares(), ColliderShape::ConvexHull, true, None);
+        let s = report.to_string();
+        assert!(!s.ends_with('\n'));
+        assert_eq!(s.lines().count(), 2 + report.islands.len());
+    }
+}
@@ -0,0 +1,295 @@
+use crate::{collider_from_edge, multi_image_edge_translated, resample_edges, ColliderShape};
+use bevy::prelude::{
+    default, warn, Assets, BuildChildren, Commands, Entity, Image, SpriteBundle, Transform,
+    TransformBundle, Vec3,
+};
+use bevy::render::texture::{CompressedImageFormats, ImageType, TextureError};
+use bevy_rapier2d::prelude::RigidBody;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// A "scene sheet", a list of sprites to load, generate colliders for and spawn in one go.
+/// Can be written as RON
+///
+/// ```ron
+/// (
+///     sprites: [
+///         (image: "assets/sprite/car.png", position: (-200.0, 2.0, 0.0), shape: ConvexPolyline, body: Dynamic),
+///         (image: "assets/sprite/terrain.png", shape: Heightfield),
+///     ],
+/// )
+/// ```
+///
+/// or the equivalent JSON. Unknown fields are an error rather than being ignored, so a typo
+/// doesn't quietly fall back to a default
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SceneSheet {
+    pub sprites: Vec<SceneSprite>,
+}
+
+/// One entry of a scene sheet
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SceneSprite {
+    /// path to the image. Relative paths are relative to the sheet's directory when it's read
+    /// with `SceneSheet::from_file`, and to the current directory otherwise
+    pub image: PathBuf,
+    /// where to spawn the sprite, (0, 0, 0) when left out
+    #[serde(default)]
+    pub position: Vec3,
+    /// the kind of collider to generate
+    pub shape: ColliderShape,
+    /// the kind of rigid body to spawn, fixed when left out
+    #[serde(default)]
+    pub body: BodyType,
+    /// whether to generate a collider for every object found in the image instead of just one
+    #[serde(default)]
+    pub multi: bool,
+    /// optionally resample the edge coordinates to roughly this segment length, see
+    /// `resample_edge`. Not allowed with `shape: Heightfield`, heightfields need one height per
+    /// pixel column
+    #[serde(default)]
+    pub resample: Option<f32>,
+}
+
+/// Serializable stand-in for bevy_rapier2d's RigidBody
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum BodyType {
+    Dynamic,
+    #[default]
+    Fixed,
+    KinematicPositionBased,
+    KinematicVelocityBased,
+}
+
+impl From<BodyType> for RigidBody {
+    fn from(body: BodyType) -> Self {
+        match body {
+            BodyType::Dynamic => RigidBody::Dynamic,
+            BodyType::Fixed => RigidBody::Fixed,
+            BodyType::KinematicPositionBased => RigidBody::KinematicPositionBased,
+            BodyType::KinematicVelocityBased => RigidBody::KinematicVelocityBased,
+        }
+    }
+}
+
+/// Everything that can go wrong reading a scene sheet or the images it lists
+#[derive(Debug)]
+pub enum SceneSheetError {
+    Io(PathBuf, std::io::Error),
+    Texture(PathBuf, TextureError),
+    Ron(ron::error::SpannedError),
+    Json(serde_json::Error),
+    UnknownFormat(PathBuf),
+    ResampleHeightfield(PathBuf),
+}
+
+impl fmt::Display for SceneSheetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SceneSheetError::Io(path, e) => write!(f, "failed to read {}: {}", path.display(), e),
+            SceneSheetError::Texture(path, e) => {
+                write!(f, "failed to load image {}: {}", path.display(), e)
+            }
+            SceneSheetError::Ron(e) => write!(f, "invalid RON scene sheet: {}", e),
+            SceneSheetError::Json(e) => write!(f, "invalid JSON scene sheet: {}", e),
+            SceneSheetError::UnknownFormat(path) => write!(
+                f,
+                "unknown scene sheet format for {}, expected a .ron or .json extension",
+                path.display()
+            ),
+            SceneSheetError::ResampleHeightfield(path) => write!(
+                f,
+                "{} asks for a resampled heightfield, heightfields can't be resampled",
+                path.display()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SceneSheetError {}
+
+impl SceneSheet {
+    /// Parse a scene sheet written in RON, relative image paths are left relative to the
+    /// current directory
+    pub fn from_ron(s: &str) -> Result<Self, SceneSheetError> {
+        ron::from_str(s).map_err(SceneSheetError::Ron)
+    }
+
+    /// Parse a scene sheet written in JSON, relative image paths are left relative to the
+    /// current directory
+    pub fn from_json(s: &str) -> Result<Self, SceneSheetError> {
+        serde_json::from_str(s).map_err(SceneSheetError::Json)
+    }
+
+    /// Read a scene sheet from a .ron or .json file, relative image paths are resolved
+    /// against the directory the sheet is in
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, SceneSheetError> {
+        let path = path.as_ref();
+        let contents =
+            std::fs::read_to_string(path).map_err(|e| SceneSheetError::Io(path.into(), e))?;
+        let mut sheet = match path.extension().and_then(|e| e.to_str()) {
+            Some("ron") => Self::from_ron(&contents)?,
+            Some("json") => Self::from_json(&contents)?,
+            _ => return Err(SceneSheetError::UnknownFormat(path.into())),
+        };
+
+        if let Some(dir) = path.parent() {
+            for sprite in &mut sheet.sprites {
+                if sprite.image.is_relative() {
+                    sprite.image = dir.join(&sprite.image);
+                }
+            }
+        }
+
+        Ok(sheet)
+    }
+}
+
+/// Loads every image listed in the scene sheet, generates its colliders and spawns it.
+/// Returns the spawned entities in the order they're listed in the sheet
+///
+/// Each sprite is spawned as one entity with its SpriteBundle and RigidBody. With `multi` set
+/// the colliders are spawned as children of that entity, otherwise the collider goes on the entity
+/// itself. Convex colliders that rapier can't build are skipped with a warning.
+///
+/// Images are read straight from the filesystem rather than through the AssetServer so
+/// everything is available by the time this returns. Every image is loaded before anything is
+/// spawned, so on error nothing has been added to `commands` or `image_assets`
+pub fn spawn_scene_sheet(
+    commands: &mut Commands,
+    image_assets: &mut Assets<Image>,
+    sheet: &SceneSheet,
+) -> Result<Vec<Entity>, SceneSheetError> {
+    if let Some(sprite) = sheet
+        .sprites
+        .iter()
+        .find(|s| s.shape == ColliderShape::Heightfield && s.resample.is_some())
+    {
+        return Err(SceneSheetError::ResampleHeightfield(sprite.image.clone()));
+    }
+
+    let images = sheet
+        .sprites
+        .iter()
+        .map(|sprite| load_image(&sprite.image))
+        .collect::<Result<Vec<Image>, SceneSheetError>>()?;
+
+    let mut entities: Vec<Entity> = vec![];
+
+    for (sprite, image) in sheet.sprites.iter().zip(images) {
+        let mut edges = multi_image_edge_translated(&image);
+        if let Some(segment_length) = sprite.resample {
+            edges = resample_edges(edges, segment_length);
+        }
+        if !sprite.multi {
+            edges = vec![edges.into_iter().flatten().collect()];
+        }
+
+        let colliders: Vec<_> = edges
+            .into_iter()
+            .filter_map(|e| {
+                let collider = collider_from_edge(sprite.shape, e);
+                if collider.is_none() {
+                    warn!(
+                        "couldn't generate a {:?} collider for {}",
+                        sprite.shape,
+                        sprite.image.display()
+                    );
+                }
+                collider
+            })
+            .collect();
+
+        let mut entity = commands.spawn((
+            RigidBody::from(sprite.body),
+            SpriteBundle {
+                texture: image_assets.add(image),
+                transform: Transform::from_translation(sprite.position),
+                ..default()
+            },
+        ));
+
+        if sprite.multi {
+            entity.with_children(|parent| {
+                for collider in colliders {
+                    parent.spawn((collider, TransformBundle::default()));
+                }
+            });
+        } else if let Some(collider) = colliders.into_iter().next() {
+            entity.insert(collider);
+        }
+
+        entities.push(entity.id());
+    }
+
+    Ok(entities)
+}
+
+/// read and decode an image from the filesystem, the format is guessed from the extension
+fn load_image(path: &Path) -> Result<Image, SceneSheetError> {
+    let bytes = std::fs::read(path).map_err(|e| SceneSheetError::Io(path.into(), e))?;
+    let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+    Image::from_buffer(
+        &bytes,
+        ImageType::Extension(extension),
+        CompressedImageFormats::NONE,
+        true,
+    )
+    .map_err(|e| SceneSheetError::Texture(path.into(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_file_resolves_images_against_the_sheet() {
+        let sheet = SceneSheet::from_file("assets/scene/example.ron").unwrap();
+        let images: Vec<PathBuf> = sheet.sprites.iter().map(|s| s.image.clone()).collect();
+        assert_eq!(
+            images,
+            vec![
+                PathBuf::from("assets/scene/../sprite/car.png"),
+                PathBuf::from("assets/scene/../sprite/terrain.png"),
+                PathBuf::from("assets/scene/../sprite/boulders.png"),
+            ]
+        );
+    }
+
+    #[test]
+    fn misspelled_field_is_an_error() {
+        let result = SceneSheet::from_ron(r#"(sprites: [(image: "car.png", shpae: Polyline)])"#);
+        assert!(matches!(result, Err(SceneSheetError::Ron(_))));
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let sheet = SceneSheet {
+            sprites: vec![SceneSprite {
+                image: "car.png".into(),
+                position: Vec3::new(-200.0, 2.0, 0.0),
+                shape: ColliderShape::ConvexPolyline,
+                body: BodyType::Dynamic,
+                multi: true,
+                resample: Some(4.0),
+            }],
+        };
+        let json = serde_json::to_string(&sheet).unwrap();
+        assert_eq!(SceneSheet::from_json(&json).unwrap(), sheet);
+    }
+
+    #[test]
+    fn json_defaults() {
+        let sheet =
+            SceneSheet::from_json(r#"{"sprites": [{"image": "car.png", "shape": "Polyline"}]}"#)
+                .unwrap();
+        let sprite = &sheet.sprites[0];
+        assert_eq!(sprite.position, Vec3::ZERO);
+        assert_eq!(sprite.body, BodyType::Fixed);
+        assert!(!sprite.multi);
+        assert_eq!(sprite.resample, None);
+    }
+}